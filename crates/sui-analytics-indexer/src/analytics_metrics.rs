@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(dead_code)]
 use prometheus::{
-    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
-    IntGaugeVec, Registry,
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, HistogramVec, IntCounterVec, IntGaugeVec, Registry,
 };
 
 #[derive(Clone)]
@@ -11,6 +11,7 @@ pub struct AnalyticsMetrics {
     pub total_received: IntCounterVec,
     pub last_uploaded_checkpoint: IntGaugeVec,
     pub max_checkpoint_on_store: IntGaugeVec,
+    pub checkpoint_processing_duration: HistogramVec,
 }
 
 impl AnalyticsMetrics {
@@ -37,6 +38,13 @@ impl AnalyticsMetrics {
                 registry,
             )
             .unwrap(),
+            checkpoint_processing_duration: register_histogram_vec_with_registry!(
+                "checkpoint_processing_duration",
+                "Time spent by the handler processing a single checkpoint, in seconds.",
+                &["data_type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
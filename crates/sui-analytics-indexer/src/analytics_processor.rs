@@ -13,7 +13,7 @@ use object_store::path::Path;
 use object_store::DynObjectStore;
 use serde::Serialize;
 use tokio::sync::{mpsc, oneshot, Mutex};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_data_ingestion_core::Worker;
@@ -22,6 +22,7 @@ use sui_storage::object_store::util::{copy_file, path_to_filesystem};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
+use crate::checksum::{write_checksum_file, CHECKSUM_FILE_SUFFIX};
 use crate::handlers::AnalyticsHandler;
 use crate::writers::AnalyticsWriter;
 use crate::{
@@ -88,8 +89,15 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
             .total_received
             .with_label_values(&[self.name()])
             .inc();
-        self.handler.process_checkpoint(checkpoint_data).await?;
-        let rows = self.handler.read().await?;
+        let rows = if self
+            .config
+            .ending_checkpoint_seq_num
+            .is_some_and(|end| checkpoint_num > end)
+        {
+            vec![]
+        } else {
+            self.process_rows(checkpoint_data).await?
+        };
         state.writer.write(&rows)?;
         state.current_checkpoint_range.end = state
             .current_checkpoint_range
@@ -127,6 +135,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             local_object_store.clone(),
             checkpoint_dir,
             config.remote_store_path_prefix.clone(),
+            config.write_checksums,
             receiver,
             kill_receiver,
             cloned_metrics,
@@ -157,6 +166,37 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         })
     }
 
+    async fn process_rows(&self, checkpoint_data: &CheckpointData) -> Result<Vec<S>> {
+        let processing_start = Instant::now();
+        if let Err(err) = self.handler.process_checkpoint(checkpoint_data).await {
+            // The handler may have buffered rows for transactions processed before the
+            // failure. Drop them so that the retried checkpoint doesn't write them twice.
+            if let Err(read_err) = self.handler.read().await {
+                error!(
+                    "Failed to drop partial rows for {} after processing error: {read_err}",
+                    self.name()
+                );
+            }
+            return Err(err);
+        }
+        let rows = self.handler.read().await?;
+        let elapsed = processing_start.elapsed();
+        self.metrics
+            .checkpoint_processing_duration
+            .with_label_values(&[self.name()])
+            .observe(elapsed.as_secs_f64());
+        warn_if_slow_checkpoint(
+            *checkpoint_data.checkpoint_summary.sequence_number(),
+            checkpoint_data.transactions.len(),
+            rows.len(),
+            elapsed,
+            self.config
+                .slow_checkpoint_threshold_ms
+                .map(Duration::from_millis),
+        );
+        Ok(rows)
+    }
+
     fn name(&self) -> &str {
         self.handler.name()
     }
@@ -222,6 +262,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
         remote_store_path_prefix: Option<Path>,
+        write_checksums: bool,
         mut file_recv: mpsc::Receiver<FileMetadata>,
         mut recv: oneshot::Receiver<()>,
         metrics: AnalyticsMetrics,
@@ -234,6 +275,17 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                     if let Some(file_metadata) = file {
                         info!("Received {name} file with checkpoints: {:?}", &file_metadata.checkpoint_seq_range);
                         let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
+                        // A missing sidecar only costs verification of this one file, so a
+                        // failure to write it must not hold up the upload of the data.
+                        let checksum_path = if write_checksums {
+                            Self::write_checksum(local_staging_root_dir.clone(), &file_metadata.file_path())
+                                .unwrap_or_else(|err| {
+                                    error!("Failed to write checksum for {name} file {}: {err}", file_metadata.file_path());
+                                    None
+                                })
+                        } else {
+                            None
+                        };
                         Self::sync_file_to_remote(
                                 local_staging_root_dir.clone(),
                                 file_metadata.file_path(),
@@ -243,6 +295,19 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                             )
                             .await
                             .expect("Syncing checkpoint should not fail");
+                        // The sidecar goes up after the data file: its name carries the same
+                        // checkpoint range, so it must never be the only file for that range.
+                        if let Some(checksum_path) = checksum_path {
+                            Self::sync_file_to_remote(
+                                    local_staging_root_dir.clone(),
+                                    checksum_path,
+                                    remote_store_path_prefix.clone(),
+                                    local_object_store.clone(),
+                                    remote_object_store.clone()
+                                )
+                                .await
+                                .expect("Syncing checksum should not fail");
+                        }
                         metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
                     } else {
                         info!("Terminating upload sync loop");
@@ -281,6 +346,21 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         Ok(())
     }
 
+    /// Writes the checksum sidecar of the staged file at `path` and returns its path. Returns
+    /// None for an empty file: `copy_file` doesn't upload those, and a sidecar without its
+    /// data file would claim a checkpoint range that isn't in the remote store.
+    fn write_checksum(dir: PathBuf, path: &Path) -> Result<Option<Path>> {
+        let local_path = path_to_filesystem(dir, path)?;
+        if fs::metadata(&local_path)?.len() == 0 {
+            return Ok(None);
+        }
+        write_checksum_file(&local_path)?;
+        Ok(Some(Path::from(format!(
+            "{}.{}",
+            path, CHECKSUM_FILE_SUFFIX
+        ))))
+    }
+
     async fn sync_file_to_remote(
         dir: PathBuf,
         path: Path,
@@ -295,3 +375,401 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         Ok(())
     }
 }
+
+/// Logs a warning if processing a checkpoint took longer than `threshold`.
+fn warn_if_slow_checkpoint(
+    checkpoint_num: u64,
+    num_transactions: usize,
+    num_rows: usize,
+    elapsed: Duration,
+    threshold: Option<Duration>,
+) {
+    if let Some(threshold) = threshold.filter(|threshold| elapsed > *threshold) {
+        warn!(
+            checkpoint_num,
+            num_transactions,
+            num_rows,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Slow checkpoint: processing took longer than {}ms",
+            threshold.as_millis()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use clap::Parser;
+    use object_store::path::Path;
+    use prometheus::Registry;
+    use serde::Serialize;
+    use simulacrum::Simulacrum;
+    use sui_analytics_indexer_derive::SerializeParquet;
+    use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+    use sui_data_ingestion_core::Worker;
+    use sui_rpc_api::CheckpointData;
+    use sui_types::base_types::{EpochId, SuiAddress};
+    use sui_types::storage::ReadStore;
+    use tokio::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    use super::AnalyticsProcessor;
+    use crate::analytics_metrics::AnalyticsMetrics;
+    use crate::checksum::verify_file;
+    use crate::handlers::package_handler::PackageHandler;
+    use crate::handlers::transaction_handler::TransactionHandler;
+    use crate::handlers::AnalyticsHandler;
+    use crate::writers::AnalyticsWriter;
+    use crate::{
+        AnalyticsIndexerConfig, FileFormat, FileType, NoOpCheckpointReader, ParquetSchema,
+        ParquetValue,
+    };
+
+    #[derive(Serialize, Clone, SerializeParquet)]
+    struct TestEntry {
+        checkpoint: u64,
+    }
+
+    /// Emits one row per checkpoint, after waiting for `delay`. When `fail_next` is set, the
+    /// row is buffered and then the checkpoint fails.
+    #[derive(Default)]
+    struct TestHandler {
+        rows: Mutex<Vec<TestEntry>>,
+        fail_next: AtomicBool,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl Worker for TestHandler {
+        type Result = ();
+
+        async fn process_checkpoint(&self, checkpoint_data: &CheckpointData) -> anyhow::Result<()> {
+            tokio::time::sleep(self.delay).await;
+            self.rows.lock().await.push(TestEntry {
+                checkpoint: *checkpoint_data.checkpoint_summary.sequence_number(),
+            });
+            if self.fail_next.swap(false, Ordering::SeqCst) {
+                anyhow::bail!("injected failure");
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AnalyticsHandler<TestEntry> for TestHandler {
+        async fn read(&self) -> anyhow::Result<Vec<TestEntry>> {
+            Ok(std::mem::take(&mut *self.rows.lock().await))
+        }
+
+        fn file_type(&self) -> anyhow::Result<FileType> {
+            Ok(FileType::Checkpoint)
+        }
+
+        fn name(&self) -> &str {
+            "test"
+        }
+    }
+
+    /// Records every row written so tests can inspect the output.
+    struct TestWriter<S> {
+        rows: std::sync::Arc<std::sync::Mutex<Vec<S>>>,
+    }
+
+    impl<S: Serialize + ParquetSchema + Clone + Send + 'static> AnalyticsWriter<S> for TestWriter<S> {
+        fn file_format(&self) -> anyhow::Result<FileFormat> {
+            Ok(FileFormat::CSV)
+        }
+
+        fn write(&mut self, rows: &[S]) -> anyhow::Result<()> {
+            self.rows.lock().unwrap().extend_from_slice(rows);
+            Ok(())
+        }
+
+        fn flush(&mut self, _end_checkpoint_seq_num: u64) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        fn reset(
+            &mut self,
+            _epoch_num: EpochId,
+            _start_checkpoint_seq_num: u64,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn file_size(&self) -> anyhow::Result<Option<u64>> {
+            Ok(None)
+        }
+    }
+
+    struct TestProcessor<S: Serialize + ParquetSchema> {
+        processor: AnalyticsProcessor<S>,
+        written: std::sync::Arc<std::sync::Mutex<Vec<S>>>,
+        _dir: tempfile::TempDir,
+    }
+
+    impl TestProcessor<TestEntry> {
+        fn written_checkpoints(&self) -> Vec<u64> {
+            let written = self.written.lock().unwrap();
+            written.iter().map(|row| row.checkpoint).collect()
+        }
+    }
+
+    async fn test_processor<S: Serialize + ParquetSchema + Clone + Send + 'static>(
+        handler: Box<dyn AnalyticsHandler<S>>,
+        next_checkpoint_seq_num: u64,
+        extra_args: &[&str],
+    ) -> anyhow::Result<TestProcessor<S>> {
+        let dir = tempfile::tempdir()?;
+        let checkpoint_dir = dir.path().join("staging");
+        let mut args = vec![
+            "sui-analytics-indexer",
+            "--rest-url",
+            "http://localhost:9000",
+            "--file-type",
+            "checkpoint",
+            "--checkpoint-dir",
+            checkpoint_dir.to_str().unwrap(),
+        ];
+        args.extend_from_slice(extra_args);
+        let mut config = AnalyticsIndexerConfig::parse_from(args);
+        config.remote_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.path().join("remote")),
+            ..Default::default()
+        };
+        let written = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let writer = TestWriter {
+            rows: written.clone(),
+        };
+        let processor = AnalyticsProcessor::new(
+            handler,
+            Box::new(writer),
+            Box::new(NoOpCheckpointReader),
+            next_checkpoint_seq_num,
+            AnalyticsMetrics::new(&Registry::new()),
+            config,
+        )
+        .await?;
+        Ok(TestProcessor {
+            processor,
+            written,
+            _dir: dir,
+        })
+    }
+
+    /// Creates a checkpoint with no transactions in it.
+    fn empty_checkpoint(sim: &mut Simulacrum) -> anyhow::Result<CheckpointData> {
+        let checkpoint = sim.create_checkpoint();
+        let contents = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)
+            .unwrap();
+        Ok(sim.get_checkpoint_data(checkpoint, contents)?)
+    }
+
+    #[test]
+    fn test_no_checksum_for_empty_file() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("0_10.csv"), b"")?;
+        std::fs::write(dir.path().join("10_20.csv"), b"0x1|0x2|100\n")?;
+
+        let empty = AnalyticsProcessor::<TestEntry>::write_checksum(
+            dir.path().to_path_buf(),
+            &Path::from("0_10.csv"),
+        )?;
+        assert!(empty.is_none());
+        assert!(!dir.path().join("0_10.csv.sha3").exists());
+
+        let non_empty = AnalyticsProcessor::<TestEntry>::write_checksum(
+            dir.path().to_path_buf(),
+            &Path::from("10_20.csv"),
+        )?;
+        assert_eq!(non_empty, Some(Path::from("10_20.csv.sha3")));
+        assert!(verify_file(&dir.path().join("10_20.csv"))?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_checkpoint_is_written_once() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+        let checkpoint = empty_checkpoint(&mut sim)?;
+        let seq = *checkpoint.checkpoint_summary.sequence_number();
+        let handler = TestHandler {
+            fail_next: AtomicBool::new(true),
+            ..Default::default()
+        };
+        let test = test_processor(Box::new(handler), seq, &[]).await?;
+
+        // The first attempt buffers a row and then fails, so nothing may be written and
+        // the range must not move.
+        assert!(test
+            .processor
+            .process_checkpoint(&checkpoint)
+            .await
+            .is_err());
+        assert!(test.written_checkpoints().is_empty());
+        assert_eq!(
+            test.processor.state.lock().await.current_checkpoint_range,
+            seq..seq
+        );
+
+        // The retry succeeds and writes the checkpoint's row exactly once.
+        test.processor.process_checkpoint(&checkpoint).await?;
+        assert_eq!(test.written_checkpoints(), vec![seq]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_rows_past_ending_checkpoint() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+        let first = empty_checkpoint(&mut sim)?;
+        let second = empty_checkpoint(&mut sim)?;
+        let first_seq = *first.checkpoint_summary.sequence_number();
+        let ending = first_seq.to_string();
+        let test = test_processor(
+            Box::new(TestHandler::default()),
+            first_seq,
+            &["--ending-checkpoint-seq-num", &ending],
+        )
+        .await?;
+
+        test.processor.process_checkpoint(&first).await?;
+        test.processor.process_checkpoint(&second).await?;
+
+        // The ending checkpoint is inclusive, and the checkpoint after it is consumed
+        // without writing anything.
+        assert_eq!(test.written_checkpoints(), vec![first_seq]);
+        let state = test.processor.state.lock().await;
+        assert_eq!(state.current_checkpoint_range, first_seq..first_seq + 2);
+        Ok(())
+    }
+
+    /// Collects the fields of every WARN event emitted while it is the default subscriber.
+    #[derive(Clone, Default)]
+    struct WarnCollector {
+        events: std::sync::Arc<std::sync::Mutex<Vec<BTreeMap<String, String>>>>,
+    }
+
+    impl<S: Subscriber> Layer<S> for WarnCollector {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            if *event.metadata().level() == Level::WARN {
+                let mut fields = FieldMap::default();
+                event.record(&mut fields);
+                self.events.lock().unwrap().push(fields.0);
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct FieldMap(BTreeMap<String, String>);
+
+    impl Visit for FieldMap {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_checkpoint_warning() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+        let fast = empty_checkpoint(&mut sim)?;
+        let slow = empty_checkpoint(&mut sim)?;
+        let fast_seq = *fast.checkpoint_summary.sequence_number();
+        let slow_seq = *slow.checkpoint_summary.sequence_number();
+        let collector = WarnCollector::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(collector.clone()),
+        );
+
+        // Nothing is logged for a checkpoint under the threshold.
+        let test = test_processor(
+            Box::new(TestHandler::default()),
+            fast_seq,
+            &["--slow-checkpoint-threshold", "1000"],
+        )
+        .await?;
+        test.processor.process_checkpoint(&fast).await?;
+        assert!(collector.events.lock().unwrap().is_empty());
+
+        let handler = TestHandler {
+            delay: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let test = test_processor(
+            Box::new(handler),
+            slow_seq,
+            &["--slow-checkpoint-threshold", "1"],
+        )
+        .await?;
+        test.processor.process_checkpoint(&slow).await?;
+
+        let events = collector.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let warning = &events[0];
+        assert!(warning["message"].starts_with("Slow checkpoint"));
+        assert_eq!(warning["checkpoint_num"], slow_seq.to_string());
+        assert_eq!(warning["num_transactions"], "0");
+        assert_eq!(warning["num_rows"], "1");
+        assert!(warning["elapsed_ms"].parse::<u64>()? >= 20);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_checkpoint_advances_range() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+        let checkpoint = empty_checkpoint(&mut sim)?;
+        assert!(checkpoint.transactions.is_empty());
+        let seq = *checkpoint.checkpoint_summary.sequence_number();
+        let test = test_processor(Box::new(TransactionHandler::new()), seq, &[]).await?;
+
+        test.processor.process_checkpoint(&checkpoint).await?;
+
+        assert!(test.written.lock().unwrap().is_empty());
+        assert_eq!(
+            test.processor.state.lock().await.current_checkpoint_range,
+            seq..seq + 1
+        );
+        let received = test
+            .processor
+            .metrics
+            .total_received
+            .with_label_values(&["transaction"])
+            .get();
+        assert_eq!(received, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_without_matching_rows_advances_range() -> anyhow::Result<()> {
+        // A plain transfer publishes no package, so the package handler emits nothing for a
+        // checkpoint that does contain a transaction.
+        let mut sim = Simulacrum::new();
+        let (transaction, _) = sim.transfer_txn(SuiAddress::random_for_testing_only());
+        let (_effects, err) = sim.execute_transaction(transaction).unwrap();
+        assert!(err.is_none());
+        let checkpoint = sim.create_checkpoint();
+        let contents = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)
+            .unwrap();
+        let checkpoint = sim.get_checkpoint_data(checkpoint, contents)?;
+        assert_eq!(checkpoint.transactions.len(), 1);
+        let seq = *checkpoint.checkpoint_summary.sequence_number();
+        let test = test_processor(Box::new(PackageHandler::new()), seq, &[]).await?;
+
+        test.processor.process_checkpoint(&checkpoint).await?;
+
+        assert!(test.written.lock().unwrap().is_empty());
+        assert_eq!(
+            test.processor.state.lock().await.current_checkpoint_range,
+            seq..seq + 1
+        );
+        Ok(())
+    }
+}
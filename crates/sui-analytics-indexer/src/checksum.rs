@@ -0,0 +1,54 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use fastcrypto::encoding::{Encoding, Hex};
+use sui_storage::compute_sha3_checksum;
+
+/// Suffix of the sidecar file holding the hex encoded SHA3-256 checksum of an output file
+pub const CHECKSUM_FILE_SUFFIX: &str = "sha3";
+
+/// Path of the checksum sidecar of `path` i.e. `0_100.parquet` -> `0_100.parquet.sha3`
+pub fn checksum_file_path(path: &Path) -> PathBuf {
+    let mut file_name = path.as_os_str().to_owned();
+    file_name.push(".");
+    file_name.push(CHECKSUM_FILE_SUFFIX);
+    PathBuf::from(file_name)
+}
+
+/// Computes the checksum of the file at `path` and writes it to its sidecar
+pub fn write_checksum_file(path: &Path) -> Result<PathBuf> {
+    let checksum_path = checksum_file_path(path);
+    fs::write(&checksum_path, Hex::encode(compute_sha3_checksum(path)?))?;
+    Ok(checksum_path)
+}
+
+/// Recomputes the checksum of the file at `path` and compares it with the one in its sidecar
+pub fn verify_file(path: &Path) -> Result<bool> {
+    let expected = fs::read_to_string(checksum_file_path(path))?;
+    Ok(expected.trim() == Hex::encode(compute_sha3_checksum(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checksum::{verify_file, write_checksum_file};
+    use std::fs;
+
+    #[test]
+    fn test_verify_file_detects_corruption() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("0_100.csv");
+        fs::write(&path, b"0x1|0x2|100\n0x3|0x4|200\n")?;
+        write_checksum_file(&path)?;
+        assert!(verify_file(&path)?);
+
+        let mut bytes = fs::read(&path)?;
+        bytes[0] ^= 0xff;
+        fs::write(&path, bytes)?;
+        assert!(!verify_file(&path)?);
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fastcrypto::encoding::{Base64, Encoding};
 use std::collections::HashMap;
 use std::path::Path;
@@ -23,7 +23,7 @@ use sui_types::dynamic_field::{DynamicFieldName, DynamicFieldType};
 use sui_types::object::Object;
 
 use crate::handlers::AnalyticsHandler;
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFetchConfig};
 use crate::tables::DynamicFieldEntry;
 use crate::FileType;
 
@@ -90,8 +90,16 @@ impl AnalyticsHandler<DynamicFieldEntry> for DynamicFieldHandler {
 }
 
 impl DynamicFieldHandler {
-    pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("dynamic_field"), rest_uri);
+    pub fn new(
+        store_path: &Path,
+        rest_uri: &str,
+        package_fetch_config: PackageFetchConfig,
+    ) -> Self {
+        let package_store = LocalDBPackageStore::new(
+            &store_path.join("dynamic_field"),
+            rest_uri,
+            package_fetch_config,
+        );
         let state = State {
             dynamic_fields: vec![],
             package_store: package_store.clone(),
@@ -213,7 +221,15 @@ impl DynamicFieldHandler {
                 &all_objects,
                 state,
             )
-            .await?;
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to process object {} in transaction {} at checkpoint {}",
+                    object.id(),
+                    checkpoint_transaction.transaction.digest(),
+                    checkpoint
+                )
+            })?;
         }
         Ok(())
     }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fastcrypto::encoding::{Base64, Encoding};
 use move_core_types::annotated_value::MoveValue;
 use sui_types::SYSTEM_PACKAGE_ADDRESSES;
@@ -11,7 +11,7 @@ use sui_data_ingestion_core::Worker;
 use tokio::sync::Mutex;
 
 use crate::handlers::AnalyticsHandler;
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFetchConfig};
 use crate::tables::EventEntry;
 use crate::FileType;
 use sui_json_rpc_types::type_and_fields_from_move_event_data;
@@ -55,7 +55,14 @@ impl Worker for EventHandler {
                     events,
                     &mut state,
                 )
-                .await?;
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to process events in transaction {} at checkpoint {}",
+                        checkpoint_transaction.transaction.digest(),
+                        checkpoint_summary.sequence_number
+                    )
+                })?;
             }
             if checkpoint_summary.end_of_epoch_data.is_some() {
                 state
@@ -87,8 +94,13 @@ impl AnalyticsHandler<EventEntry> for EventHandler {
 }
 
 impl EventHandler {
-    pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("event"), rest_uri);
+    pub fn new(
+        store_path: &Path,
+        rest_uri: &str,
+        package_fetch_config: PackageFetchConfig,
+    ) -> Self {
+        let package_store =
+            LocalDBPackageStore::new(&store_path.join("event"), rest_uri, package_fetch_config);
         let state = State {
             events: vec![],
             package_store: package_store.clone(),
@@ -40,7 +40,8 @@ const WRAPPED_INDEXING_DISALLOW_LIST: [&str; 4] = [
 pub trait AnalyticsHandler<S>: Worker<Result = ()> {
     /// Read back rows which are ready to be persisted. This function
     /// will be invoked by the analytics processor after every call to
-    /// process_checkpoint
+    /// process_checkpoint. If process_checkpoint fails, the rows returned
+    /// here are discarded so that a checkpoint is written all or nothing
     async fn read(&self) -> Result<Vec<S>>;
     /// Type of data being written by this processor i.e. checkpoint, object, etc
     fn file_type(&self) -> Result<FileType>;
@@ -62,8 +63,7 @@ fn get_owner_type(object: &Object) -> OwnerType {
         Owner::ObjectOwner(_) => OwnerType::ObjectOwner,
         Owner::Shared { .. } => OwnerType::Shared,
         Owner::Immutable => OwnerType::Immutable,
-        // TODO: Implement support for ConsensusV2 objects.
-        Owner::ConsensusV2 { .. } => todo!(),
+        Owner::ConsensusV2 { .. } => OwnerType::ConsensusAddress,
     }
 }
 
@@ -73,8 +73,11 @@ fn get_owner_address(object: &Object) -> Option<String> {
         Owner::ObjectOwner(address) => Some(address.to_string()),
         Owner::Shared { .. } => None,
         Owner::Immutable => None,
-        // TODO: Implement support for ConsensusV2 objects.
-        Owner::ConsensusV2 { .. } => todo!(),
+        // ConsensusV2 objects are treated as singly-owned for now.
+        // This will need to be updated if additional Authenticators are added.
+        Owner::ConsensusV2 {
+            ref authenticator, ..
+        } => Some(authenticator.as_single_owner().to_string()),
     }
 }
 
@@ -287,14 +290,17 @@ fn parse_struct_field(
 
 #[cfg(test)]
 mod tests {
-    use crate::handlers::parse_struct;
+    use crate::handlers::{get_owner_address, get_owner_type, parse_struct};
+    use crate::tables::OwnerType;
     use move_core_types::account_address::AccountAddress;
     use move_core_types::annotated_value::{MoveStruct, MoveValue, MoveVariant};
     use move_core_types::identifier::Identifier;
     use move_core_types::language_storage::StructTag;
     use std::collections::BTreeMap;
     use std::str::FromStr;
-    use sui_types::base_types::ObjectID;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::object::{Authenticator, MoveObject, Object, Owner};
 
     #[tokio::test]
     async fn test_wrapped_object_parsing() -> anyhow::Result<()> {
@@ -388,4 +394,44 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_consensus_v2_owner() -> anyhow::Result<()> {
+        // A coin moves from an address owner into consensus ownership and back. The
+        // owner address must be reported the same way at every step.
+        let owner = SuiAddress::random_for_testing_only();
+        let coin_id = ObjectID::random();
+        let coin_at = |version: u64, owner: Owner| {
+            Object::new_move(
+                MoveObject::new_gas_coin(SequenceNumber::from_u64(version), coin_id, 10),
+                owner,
+                TransactionDigest::genesis_marker(),
+            )
+        };
+
+        let address_owned = coin_at(1, Owner::AddressOwner(owner));
+        assert!(matches!(
+            get_owner_type(&address_owned),
+            OwnerType::AddressOwner
+        ));
+        assert_eq!(get_owner_address(&address_owned), Some(owner.to_string()));
+
+        let consensus_owned = coin_at(
+            2,
+            Owner::ConsensusV2 {
+                start_version: SequenceNumber::from_u64(2),
+                authenticator: Box::new(Authenticator::SingleOwner(owner)),
+            },
+        );
+        assert!(matches!(
+            get_owner_type(&consensus_owned),
+            OwnerType::ConsensusAddress
+        ));
+        assert_eq!(get_owner_address(&consensus_owned), Some(owner.to_string()));
+
+        let returned = coin_at(3, Owner::AddressOwner(owner));
+        assert!(matches!(get_owner_type(&returned), OwnerType::AddressOwner));
+        assert_eq!(get_owner_address(&returned), Some(owner.to_string()));
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fastcrypto::encoding::{Base64, Encoding};
 use std::path::Path;
 use sui_data_ingestion_core::Worker;
@@ -20,7 +20,7 @@ use crate::handlers::{
     ObjectStatusTracker,
 };
 
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFetchConfig};
 use crate::tables::{ObjectEntry, ObjectStatus};
 use crate::FileType;
 
@@ -89,8 +89,14 @@ impl AnalyticsHandler<ObjectEntry> for ObjectHandler {
 }
 
 impl ObjectHandler {
-    pub fn new(store_path: &Path, rest_uri: &str, package_filter: &Option<String>) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("object"), rest_uri);
+    pub fn new(
+        store_path: &Path,
+        rest_uri: &str,
+        package_filter: &Option<String>,
+        package_fetch_config: PackageFetchConfig,
+    ) -> Self {
+        let package_store =
+            LocalDBPackageStore::new(&store_path.join("object"), rest_uri, package_fetch_config);
         let state = State {
             objects: vec![],
             package_store: package_store.clone(),
@@ -122,7 +128,15 @@ impl ObjectHandler {
                 &object_status_tracker,
                 state,
             )
-            .await?;
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to process object {} in transaction {} at checkpoint {}",
+                    object.id(),
+                    checkpoint_transaction.transaction.digest(),
+                    checkpoint
+                )
+            })?;
         }
         for (object_ref, _) in effects.all_removed_objects().iter() {
             let entry = ObjectEntry {
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fastcrypto::encoding::{Base64, Encoding};
 use sui_data_ingestion_core::Worker;
 use sui_rpc_api::CheckpointData;
@@ -77,7 +77,15 @@ impl PackageHandler {
         state: &mut State,
     ) -> Result<()> {
         for object in checkpoint_transaction.output_objects.iter() {
-            self.process_package(epoch, checkpoint, timestamp_ms, object, state)?;
+            self.process_package(epoch, checkpoint, timestamp_ms, object, state)
+                .with_context(|| {
+                    format!(
+                        "Failed to process package {} in transaction {} at checkpoint {}",
+                        object.id(),
+                        checkpoint_transaction.transaction.digest(),
+                        checkpoint
+                    )
+                })?;
         }
         Ok(())
     }
@@ -3,7 +3,7 @@
 
 use std::collections::BTreeSet;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fastcrypto::encoding::{Base64, Encoding};
 use sui_data_ingestion_core::Worker;
 use tokio::sync::Mutex;
@@ -45,7 +45,14 @@ impl Worker for TransactionHandler {
                 checkpoint_transaction,
                 &checkpoint_transaction.effects,
                 &mut state,
-            )?;
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to process transaction {} at checkpoint {}",
+                    checkpoint_transaction.transaction.digest(),
+                    checkpoint_summary.sequence_number
+                )
+            })?;
         }
         Ok(())
     }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use sui_data_ingestion_core::Worker;
 use tokio::sync::Mutex;
 
@@ -41,7 +41,14 @@ impl Worker for TransactionObjectsHandler {
                 checkpoint_transaction,
                 &checkpoint_transaction.effects,
                 &mut state,
-            );
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to process transaction {} at checkpoint {}",
+                    checkpoint_transaction.transaction.digest(),
+                    checkpoint_summary.sequence_number
+                )
+            })?;
         }
         Ok(())
     }
@@ -81,7 +88,7 @@ impl TransactionObjectsHandler {
         checkpoint_transaction: &CheckpointTransaction,
         effects: &TransactionEffects,
         state: &mut State,
-    ) {
+    ) -> Result<()> {
         let transaction = &checkpoint_transaction.transaction;
         let transaction_digest = transaction.digest().base58_encode();
         let txn_data = transaction.transaction_data();
@@ -89,8 +96,7 @@ impl TransactionObjectsHandler {
         let object_status_tracker = ObjectStatusTracker::new(effects);
         // input
         txn_data
-            .input_objects()
-            .expect("Input objects must be valid")
+            .input_objects()?
             .iter()
             .map(|object| (object.object_id(), object.version().map(|v| v.value())))
             .for_each(|(object_id, version)| {
@@ -124,6 +130,7 @@ impl TransactionObjectsHandler {
                     state,
                 )
             });
+        Ok(())
     }
     // Transaction object data.
     // Builds a view of the object in input and output of a transaction.
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::BTreeMap;
 use std::path::Path;
 use sui_data_ingestion_core::Worker;
@@ -14,7 +14,7 @@ use sui_types::object::Object;
 
 use crate::handlers::{get_move_struct, parse_struct, AnalyticsHandler};
 
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFetchConfig};
 use crate::tables::WrappedObjectEntry;
 use crate::FileType;
 
@@ -81,8 +81,16 @@ impl AnalyticsHandler<WrappedObjectEntry> for WrappedObjectHandler {
 }
 
 impl WrappedObjectHandler {
-    pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("wrapped_object"), rest_uri);
+    pub fn new(
+        store_path: &Path,
+        rest_uri: &str,
+        package_fetch_config: PackageFetchConfig,
+    ) -> Self {
+        let package_store = LocalDBPackageStore::new(
+            &store_path.join("wrapped_object"),
+            rest_uri,
+            package_fetch_config,
+        );
         let state = Mutex::new(State {
             wrapped_objects: vec![],
             package_store: package_store.clone(),
@@ -100,7 +108,15 @@ impl WrappedObjectHandler {
     ) -> Result<()> {
         for object in checkpoint_transaction.output_objects.iter() {
             self.process_object(epoch, checkpoint, timestamp_ms, object, state)
-                .await?;
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to process object {} in transaction {} at checkpoint {}",
+                        object.id(),
+                        checkpoint_transaction.transaction.digest(),
+                        checkpoint
+                    )
+                })?;
         }
         Ok(())
     }
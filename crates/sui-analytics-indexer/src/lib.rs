@@ -3,6 +3,7 @@
 
 use std::ops::Range;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use arrow_array::{Array, Int32Array};
@@ -39,6 +40,7 @@ use crate::handlers::transaction_handler::TransactionHandler;
 use crate::handlers::transaction_objects_handler::TransactionObjectsHandler;
 use crate::handlers::wrapped_object_handler::WrappedObjectHandler;
 use crate::handlers::AnalyticsHandler;
+use crate::package_store::PackageFetchConfig;
 use crate::tables::{
     CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
     MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
@@ -50,6 +52,7 @@ use crate::writers::AnalyticsWriter;
 
 pub mod analytics_metrics;
 pub mod analytics_processor;
+pub mod checksum;
 pub mod errors;
 mod handlers;
 mod package_store;
@@ -96,6 +99,10 @@ pub struct AnalyticsIndexerConfig {
     /// Checkpoint sequence number to start the download from
     #[clap(long, default_value = None, global = true)]
     pub starting_checkpoint_seq_num: Option<u64>,
+    /// Last checkpoint (inclusive) to write rows for. Later checkpoints are still consumed so
+    /// the worker keeps advancing, they just don't produce any rows
+    #[clap(long, default_value = None, global = true)]
+    pub ending_checkpoint_seq_num: Option<u64>,
     /// Time to process in seconds before uploading to the datastore.
     #[clap(long, default_value = "600", global = true)]
     pub time_interval_s: u64,
@@ -160,6 +167,29 @@ pub struct AnalyticsIndexerConfig {
     pub report_sf_max_table_checkpoint: bool,
     #[clap(long, default_value = None, global = true)]
     pub package_id_filter: Option<String>,
+    /// Number of times a failed package fetch from the full node is retried
+    #[clap(long, default_value = "5", global = true)]
+    pub package_fetch_max_retries: u32,
+    /// Delay in milliseconds before the first package fetch retry, grows exponentially
+    #[clap(long, default_value = "100", global = true)]
+    pub package_fetch_initial_backoff_ms: u64,
+    /// Upload a `.sha3` sidecar with the SHA3-256 checksum of every non-empty output file.
+    /// SHA3-256 rather than SHA-256, to match the checksums used elsewhere in sui-storage
+    #[clap(long, global = true)]
+    pub write_checksums: bool,
+    /// Log a warning for every checkpoint whose processing takes longer than this many
+    /// milliseconds
+    #[clap(long = "slow-checkpoint-threshold", default_value = None, global = true)]
+    pub slow_checkpoint_threshold_ms: Option<u64>,
+}
+
+impl AnalyticsIndexerConfig {
+    fn package_fetch_config(&self) -> PackageFetchConfig {
+        PackageFetchConfig {
+            max_retries: self.package_fetch_max_retries,
+            initial_backoff: Duration::from_millis(self.package_fetch_initial_backoff_ms),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -693,6 +723,7 @@ pub async fn make_object_processor(
         &config.package_cache_path,
         &config.rest_url,
         &config.package_id_filter,
+        config.package_fetch_config(),
     ));
     let starting_checkpoint_seq_num =
         get_starting_checkpoint_seq_num(config.clone(), FileType::Object).await?;
@@ -720,6 +751,7 @@ pub async fn make_event_processor(
     let handler: Box<dyn AnalyticsHandler<EventEntry>> = Box::new(EventHandler::new(
         &config.package_cache_path,
         &config.rest_url,
+        config.package_fetch_config(),
     ));
     let starting_checkpoint_seq_num =
         get_starting_checkpoint_seq_num(config.clone(), FileType::Event).await?;
@@ -818,6 +850,7 @@ pub async fn make_dynamic_field_processor(
     let handler: Box<dyn AnalyticsHandler<DynamicFieldEntry>> = Box::new(DynamicFieldHandler::new(
         &config.package_cache_path,
         &config.rest_url,
+        config.package_fetch_config(),
     ));
     let writer = make_writer::<DynamicFieldEntry>(
         config.clone(),
@@ -842,9 +875,12 @@ pub async fn make_wrapped_object_processor(
 ) -> Result<Processor> {
     let starting_checkpoint_seq_num =
         get_starting_checkpoint_seq_num(config.clone(), FileType::WrappedObject).await?;
-    let handler: Box<dyn AnalyticsHandler<WrappedObjectEntry>> = Box::new(
-        WrappedObjectHandler::new(&config.package_cache_path, &config.rest_url),
-    );
+    let handler: Box<dyn AnalyticsHandler<WrappedObjectEntry>> =
+        Box::new(WrappedObjectHandler::new(
+            &config.package_cache_path,
+            &config.rest_url,
+            config.package_fetch_config(),
+        ));
     let writer = make_writer::<WrappedObjectEntry>(
         config.clone(),
         FileType::WrappedObject,
@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_trait::async_trait;
+use std::future::Future;
 use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use move_core_types::account_address::AccountAddress;
 use sui_package_resolver::{
@@ -13,6 +16,7 @@ use sui_rpc_api::Client;
 use sui_types::base_types::ObjectID;
 use sui_types::object::Object;
 use thiserror::Error;
+use tracing::{error, warn};
 use typed_store::rocks::{DBMap, MetricConf};
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
@@ -62,6 +66,15 @@ impl PackageStoreTables {
     }
 }
 
+/// Controls how packages missing from the local store are fetched from the full node.
+#[derive(Clone, Debug)]
+pub struct PackageFetchConfig {
+    /// Number of times a failed fetch is retried before giving up
+    pub max_retries: u32,
+    /// Delay before the first retry, grows exponentially (with jitter) on every attempt
+    pub initial_backoff: Duration,
+}
+
 /// Store which keeps package objects in a local rocksdb store. It is expected that this store is
 /// kept updated with latest version of package objects while iterating over checkpoints. If the
 /// local db is missing (or gets deleted), packages are fetched from a full node and local store is
@@ -70,13 +83,15 @@ impl PackageStoreTables {
 pub struct LocalDBPackageStore {
     package_store_tables: Arc<PackageStoreTables>,
     fallback_client: Client,
+    fetch_config: PackageFetchConfig,
 }
 
 impl LocalDBPackageStore {
-    pub fn new(path: &Path, rest_url: &str) -> Self {
+    pub fn new(path: &Path, rest_url: &str, fetch_config: PackageFetchConfig) -> Self {
         Self {
             package_store_tables: PackageStoreTables::new(path),
             fallback_client: Client::new(rest_url).unwrap(),
+            fetch_config,
         }
     }
 
@@ -97,11 +112,20 @@ impl LocalDBPackageStore {
         {
             object
         } else {
-            let object = self
-                .fallback_client
-                .get_object(ObjectID::from(id))
-                .await
-                .map_err(|_| PackageResolverError::PackageNotFound(id))?;
+            let object = retry_with_backoff(
+                &self.fetch_config,
+                || self.fallback_client.get_object(ObjectID::from(id)),
+                is_not_found,
+            )
+            .await
+            .map_err(|status| {
+                if is_not_found(&status) {
+                    PackageResolverError::PackageNotFound(id)
+                } else {
+                    error!("Failed to fetch package {id} from the full node: {status}");
+                    PackageResolverError::UnexpectedError(Arc::new(status))
+                }
+            })?;
             self.update(&object)?;
             object
         };
@@ -118,3 +142,115 @@ impl PackageStore for LocalDBPackageStore {
 }
 
 pub(crate) type PackageCache = PackageStoreWithLruCache<LocalDBPackageStore>;
+
+/// A package the full node doesn't know about won't show up by asking again.
+fn is_not_found(status: &tonic::Status) -> bool {
+    status.code() == tonic::Code::NotFound
+}
+
+/// Runs `fetch` until it succeeds, retrying with exponential backoff up to
+/// `config.max_retries` times. Errors for which `is_permanent` returns true are not retried.
+/// Only the last error is returned to the caller.
+async fn retry_with_backoff<T, E, F, Fut, P>(
+    config: &PackageFetchConfig,
+    fetch: F,
+    is_permanent: P,
+) -> std::result::Result<T, E>
+where
+    E: std::fmt::Display,
+    F: Fn() -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    P: Fn(&E) -> bool,
+{
+    let attempts = AtomicU32::new(0);
+    let backoff = backoff::ExponentialBackoff {
+        initial_interval: config.initial_backoff,
+        max_elapsed_time: None,
+        ..Default::default()
+    };
+    backoff::future::retry(backoff, || async {
+        let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+        fetch().await.map_err(|err| {
+            if attempt < config.max_retries && !is_permanent(&err) {
+                warn!("Package fetch failed (attempt {}): {}", attempt + 1, err);
+                backoff::Error::transient(err)
+            } else {
+                backoff::Error::permanent(err)
+            }
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_with_backoff, PackageFetchConfig};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_fetch_retries_transient_errors() -> anyhow::Result<()> {
+        let config = PackageFetchConfig {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(1),
+        };
+        let calls = AtomicU32::new(0);
+        let result: Result<u64, String> = retry_with_backoff(
+            &config,
+            || async {
+                if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+                    Err("unavailable".to_string())
+                } else {
+                    Ok(42)
+                }
+            },
+            |_| false,
+        )
+        .await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_gives_up_after_max_retries() -> anyhow::Result<()> {
+        let config = PackageFetchConfig {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+        };
+        let calls = AtomicU32::new(0);
+        let result: Result<u64, String> = retry_with_backoff(
+            &config,
+            || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err("unavailable".to_string())
+            },
+            |_| false,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_does_not_retry_permanent_errors() -> anyhow::Result<()> {
+        let config = PackageFetchConfig {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(1),
+        };
+        let calls = AtomicU32::new(0);
+        let result: Result<u64, String> = retry_with_backoff(
+            &config,
+            || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err("not found".to_string())
+            },
+            |err| err == "not found",
+        )
+        .await;
+        assert_eq!(result, Err("not found".to_string()));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        Ok(())
+    }
+}
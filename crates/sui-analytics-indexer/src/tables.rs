@@ -148,6 +148,7 @@ pub enum OwnerType {
     ObjectOwner,
     Shared,
     Immutable,
+    ConsensusAddress,
 }
 
 // Object information.